@@ -0,0 +1,198 @@
+//! Runtime conversion traits consumed by the code `jni_verify::verify_signature`
+//! generates. This lives in its own (non-proc-macro) crate because the glue
+//! the macro emits needs `FromJava`/`IntoJava` as ordinary, linkable items,
+//! which a `proc-macro = true` crate can't export.
+
+use jni::objects::{JIntArray, JObject, JString};
+use jni::sys::jint;
+use jni::JNIEnv;
+
+/// Re-exported so `jni_verify` (the proc-macro crate) can name the concrete
+/// `Uuid` type it needs for signature verification without depending on the
+/// `uuid` crate itself.
+pub use uuid::Uuid;
+
+/// Converts a raw JNI value into an idiomatic Rust type for a native method
+/// parameter.
+pub trait FromJava<'local>: Sized {
+    type Raw;
+
+    /// The fully qualified Java type this conversion applies to, e.g.
+    /// `"java.lang.String"` or `"[Ljava/lang/String;"`.
+    fn java_type() -> String;
+
+    fn from_java(env: &mut JNIEnv<'local>, raw: Self::Raw) -> Self;
+}
+
+/// Converts an idiomatic Rust type into a raw JNI value for a native method
+/// return.
+pub trait IntoJava<'local> {
+    type Raw;
+
+    /// The fully qualified Java type this conversion applies to, e.g.
+    /// `"java.lang.String"` or `"[Ljava/lang/String;"`.
+    fn java_type() -> String;
+
+    fn into_java(self, env: &mut JNIEnv<'local>) -> Self::Raw;
+}
+
+impl<'local> FromJava<'local> for String {
+    type Raw = JObject<'local>;
+
+    fn java_type() -> String {
+        "java.lang.String".to_string()
+    }
+
+    fn from_java(env: &mut JNIEnv<'local>, raw: Self::Raw) -> Self {
+        let raw = JString::from(raw);
+        env.get_string(&raw)
+            .expect("invalid UTF-8 in Java string")
+            .into()
+    }
+}
+
+impl<'local> IntoJava<'local> for String {
+    type Raw = JObject<'local>;
+
+    fn java_type() -> String {
+        "java.lang.String".to_string()
+    }
+
+    fn into_java(self, env: &mut JNIEnv<'local>) -> Self::Raw {
+        env.new_string(self)
+            .expect("failed to allocate Java string")
+            .into()
+    }
+}
+
+impl<'local> FromJava<'local> for uuid::Uuid {
+    type Raw = JObject<'local>;
+
+    fn java_type() -> String {
+        "java.util.UUID".to_string()
+    }
+
+    fn from_java(env: &mut JNIEnv<'local>, raw: Self::Raw) -> Self {
+        let msb = env
+            .call_method(&raw, "getMostSignificantBits", "()J", &[])
+            .and_then(|v| v.j())
+            .expect("java.util.UUID::getMostSignificantBits failed") as u64;
+        let lsb = env
+            .call_method(&raw, "getLeastSignificantBits", "()J", &[])
+            .and_then(|v| v.j())
+            .expect("java.util.UUID::getLeastSignificantBits failed") as u64;
+        uuid::Uuid::from_u64_pair(msb, lsb)
+    }
+}
+
+impl<'local> IntoJava<'local> for uuid::Uuid {
+    type Raw = JObject<'local>;
+
+    fn java_type() -> String {
+        "java.util.UUID".to_string()
+    }
+
+    fn into_java(self, env: &mut JNIEnv<'local>) -> Self::Raw {
+        let (msb, lsb) = self.as_u64_pair();
+        env.new_object(
+            "java/util/UUID",
+            "(JJ)V",
+            &[(msb as i64).into(), (lsb as i64).into()],
+        )
+        .expect("failed to construct java.util.UUID")
+    }
+}
+
+impl<'local, T> FromJava<'local> for Vec<T>
+where
+    T: FromJava<'local, Raw = JObject<'local>>,
+{
+    type Raw = jni::objects::JObjectArray<'local>;
+
+    fn java_type() -> String {
+        format!("[L{};", T::java_type().replace('.', "/"))
+    }
+
+    fn from_java(env: &mut JNIEnv<'local>, raw: Self::Raw) -> Self {
+        let len = env
+            .get_array_length(&raw)
+            .expect("failed to get Java array length");
+        (0..len)
+            .map(|i| {
+                let element = env
+                    .get_object_array_element(&raw, i)
+                    .expect("failed to read Java array element");
+                T::from_java(env, element)
+            })
+            .collect()
+    }
+}
+
+impl<'local, T> IntoJava<'local> for Vec<T>
+where
+    T: IntoJava<'local, Raw = JObject<'local>>,
+{
+    type Raw = jni::objects::JObjectArray<'local>;
+
+    fn java_type() -> String {
+        format!("[L{};", T::java_type().replace('.', "/"))
+    }
+
+    fn into_java(self, env: &mut JNIEnv<'local>) -> Self::Raw {
+        let class_name = T::java_type().replace('.', "/");
+        let class = env
+            .find_class(&class_name)
+            .unwrap_or_else(|_| panic!("failed to find Java class {class_name}"));
+        let array = env
+            .new_object_array(self.len() as jint, class, JObject::null())
+            .expect("failed to allocate Java array");
+        for (i, value) in self.into_iter().enumerate() {
+            let element = value.into_java(env);
+            env.set_object_array_element(&array, i as jint, element)
+                .expect("failed to write Java array element");
+        }
+        array
+    }
+}
+
+/// Vec<i32> maps to a Java primitive `int[]`, rather than an object array.
+///
+/// `Raw` is the already-validated `JIntArray` wrapper rather than the raw
+/// `jintArray` pointer, so `from_java` never needs to call the `unsafe`
+/// `JPrimitiveArray::from_raw` itself — the generated `extern "system"`
+/// wrapper receives a `JIntArray` directly off the JNI call, the same way
+/// it already receives `JObject`/`JString` parameters.
+impl<'local> FromJava<'local> for Vec<i32> {
+    type Raw = JIntArray<'local>;
+
+    fn java_type() -> String {
+        "[I".to_string()
+    }
+
+    fn from_java(env: &mut JNIEnv<'local>, raw: Self::Raw) -> Self {
+        let len = env
+            .get_array_length(&raw)
+            .expect("failed to get Java array length");
+        let mut buf = vec![0i32; len as usize];
+        env.get_int_array_region(&raw, 0, &mut buf)
+            .expect("failed to read Java int[] contents");
+        buf
+    }
+}
+
+impl<'local> IntoJava<'local> for Vec<i32> {
+    type Raw = JIntArray<'local>;
+
+    fn java_type() -> String {
+        "[I".to_string()
+    }
+
+    fn into_java(self, env: &mut JNIEnv<'local>) -> Self::Raw {
+        let array = env
+            .new_int_array(self.len() as jint)
+            .expect("failed to allocate Java int[]");
+        env.set_int_array_region(&array, 0, &self)
+            .expect("failed to write Java int[] contents");
+        array
+    }
+}
@@ -1,97 +1,546 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
 
-use lazy_static::lazy_static;
+use jni_verify_types::FromJava;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::{abort, proc_macro_error};
-use regex::Regex;
+use quote::quote;
 use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, FnArg, ItemFn, LitStr, ReturnType, Token, TypePath, TypeTuple};
+use syn::{parse_macro_input, FnArg, Ident, ItemFn, LitStr, ReturnType, Token, TypePath};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Primitive {
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Void,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JavaType {
+    Primitive(Primitive),
+    Object(String),
+    Array(Box<JavaType>),
+    Method {
+        params: Vec<JavaType>,
+        ret: Box<JavaType>,
+    },
+}
+
+struct DescriptorParseError {
+    offset: usize,
+    message: String,
+}
+
+/// Recursive-descent parser over a JVM-style type descriptor (`(...)...`).
+///
+/// Note that, matching this crate's existing signature strings, fully
+/// qualified class names are written with `.` rather than the `/` used in
+/// real class files (e.g. `Ljava.lang.String;`).
+struct DescriptorParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> DescriptorParser<'a> {
+    fn new(input: &'a str) -> Self {
+        DescriptorParser { input, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> DescriptorParseError {
+        DescriptorParseError {
+            offset: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn parse_type(&mut self) -> std::result::Result<JavaType, DescriptorParseError> {
+        match self.peek() {
+            Some('Z') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Boolean))
+            }
+            Some('B') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Byte))
+            }
+            Some('C') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Char))
+            }
+            Some('S') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Short))
+            }
+            Some('I') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Int))
+            }
+            Some('J') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Long))
+            }
+            Some('F') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Float))
+            }
+            Some('D') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Double))
+            }
+            Some('V') => {
+                self.bump();
+                Ok(JavaType::Primitive(Primitive::Void))
+            }
+            Some('L') => self.parse_object(),
+            Some('[') => {
+                self.bump();
+                Ok(JavaType::Array(Box::new(self.parse_type()?)))
+            }
+            Some(other) => Err(self.error(format!(
+                "unexpected character `{}`, expected a type descriptor",
+                other
+            ))),
+            None => Err(self.error("unexpected end of descriptor, expected a type")),
+        }
+    }
+
+    fn parse_object(&mut self) -> std::result::Result<JavaType, DescriptorParseError> {
+        let start = self.pos;
+        self.bump(); // consume 'L'
+        let name_start = self.pos;
+        loop {
+            match self.bump() {
+                Some(';') => {
+                    return Ok(JavaType::Object(
+                        self.input[name_start..self.pos - 1].to_string(),
+                    ))
+                }
+                Some(_) => continue,
+                None => {
+                    return Err(DescriptorParseError {
+                        offset: start,
+                        message: "unterminated object type, expected a closing `;`".to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    fn parse_method(&mut self) -> std::result::Result<JavaType, DescriptorParseError> {
+        if self.bump() != Some('(') {
+            return Err(self.error("expected `(` at the start of the signature"));
+        }
+
+        let mut params = Vec::new();
+        while self.peek() != Some(')') {
+            if self.peek().is_none() {
+                return Err(self.error("unterminated parameter list, expected `)`"));
+            }
+            params.push(self.parse_type()?);
+        }
+        self.bump(); // consume ')'
+
+        let ret = self.parse_type()?;
+        if let Some(trailing) = self.peek() {
+            return Err(self.error(format!(
+                "unexpected trailing character `{}` after return type",
+                trailing
+            )));
+        }
+
+        Ok(JavaType::Method {
+            params,
+            ret: Box::new(ret),
+        })
+    }
+}
+
+impl JavaType {
+    fn parse(descriptor: &str) -> std::result::Result<JavaType, DescriptorParseError> {
+        DescriptorParser::new(descriptor).parse_method()
+    }
+
+    /// Parses a single field descriptor (e.g. `Ljava.lang.String;` or
+    /// `[I`), as opposed to a full `(...)...` method descriptor.
+    fn parse_field(descriptor: &str) -> std::result::Result<JavaType, DescriptorParseError> {
+        let mut parser = DescriptorParser::new(descriptor);
+        let java_type = parser.parse_type()?;
+        if let Some(trailing) = parser.peek() {
+            return Err(parser.error(format!("unexpected trailing character `{}`", trailing)));
+        }
+        Ok(java_type)
+    }
+}
 
 struct Signature {
     span: Span,
-    params: Vec<String>,
-    ret: String,
+    params: Vec<JavaType>,
+    ret: JavaType,
+}
+
+/// Where the JNI symbol name comes from: either hand-written by the user
+/// (the original `#[verify_signature("foo", "(...)...")]` form) or computed
+/// by the macro from the Java `package`/`class`/`method` it implements.
+enum NameSpec {
+    Explicit(LitStr),
+    Generated {
+        package: Option<String>,
+        class: String,
+        method: String,
+    },
 }
 
 struct Args {
-    name: LitStr,
+    name_spec: NameSpec,
     signature: Signature,
+    exception: Option<String>,
 }
 
-lazy_static! {
-    static ref SIGNATURE_REGEX: Regex = Regex::new(r"\((?<params>.*)\)(?<ret>.+)").unwrap();
-    static ref TYPE_REGEX: Regex = Regex::new(r"\[?(?:[^IJBZCSFDVL;]*[IJBZCSFDV][^IJBZCSFDVL;\[]*|L[\w\.]+\;)").unwrap();
+/// The Java exception class thrown when an `exception = "..."` function
+/// body returns `Err` and no explicit class was given.
+const DEFAULT_EXCEPTION_CLASS: &str = "java.lang.RuntimeException";
+
+/// A single `key = "value"` pair, as used by the named attribute form.
+struct NamedArg {
+    key: Ident,
+    value: LitStr,
+}
+
+impl Parse for NamedArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: LitStr = input.parse()?;
+        Ok(NamedArg { key, value })
+    }
+}
+
+fn parse_signature(signature: &LitStr) -> Signature {
+    let method = JavaType::parse(&signature.value()).unwrap_or_else(|err| {
+        abort!(
+            signature,
+            "Invalid signature at byte offset {}: {}",
+            err.offset,
+            err.message
+        )
+    });
+
+    let (params, ret) = match method {
+        JavaType::Method { params, ret } => (params, *ret),
+        _ => unreachable!("DescriptorParser::parse_method always returns a Method"),
+    };
+
+    Signature {
+        span: signature.span(),
+        params,
+        ret,
+    }
 }
 
 impl Parse for Args {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut vars = Punctuated::<LitStr, Token![,]>::parse_terminated(input)?.into_iter();
+        if input.peek(LitStr) {
+            let name: LitStr = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let signature: LitStr = input.parse()?;
+
+            let mut exception = None;
+            while input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                if input.is_empty() {
+                    break;
+                }
+                let pair: NamedArg = input.parse()?;
+                match pair.key.to_string().as_str() {
+                    "exception" => exception = Some(pair.value.value()),
+                    other => abort!(pair.key, "Unknown key `{}`, expected `exception`", other),
+                }
+            }
+
+            return Ok(Args {
+                name_spec: NameSpec::Explicit(name),
+                signature: parse_signature(&signature),
+                exception,
+            });
+        }
 
-        let name = vars.next().unwrap();
-        let signature = vars.next().unwrap();
-        let sig = signature.value();
+        let pairs = Punctuated::<NamedArg, Token![,]>::parse_terminated(input)?;
 
-        let matches = SIGNATURE_REGEX
-            .captures(&sig)
-            .unwrap_or_else(|| abort!(signature, "Invalid signature, expected `(...)...`"));
+        let mut package = None;
+        let mut class = None;
+        let mut method = None;
+        let mut sig = None;
+        let mut exception = None;
 
-        let params_haystack = &matches["params"];
-        let ret = &matches["ret"];
+        for pair in pairs {
+            match pair.key.to_string().as_str() {
+                "package" => package = Some(pair.value),
+                "class" => class = Some(pair.value),
+                "method" => method = Some(pair.value),
+                "sig" => sig = Some(pair.value),
+                "exception" => exception = Some(pair.value),
+                other => abort!(
+                    pair.key,
+                    "Unknown key `{}`, expected one of `package`, `class`, `method`, `sig`, `exception`",
+                    other
+                ),
+            }
+        }
 
-        let params: Vec<_> = TYPE_REGEX
-            .captures_iter(params_haystack)
-            .map(|capture_match| capture_match.extract::<0>().0.to_string())
-            .collect();
+        let class = class.unwrap_or_else(|| abort!(input.span(), "Missing required `class = \"...\"`"));
+        let method = method.unwrap_or_else(|| abort!(input.span(), "Missing required `method = \"...\"`"));
+        let sig = sig.unwrap_or_else(|| abort!(input.span(), "Missing required `sig = \"...\"`"));
 
         Ok(Args {
-            name,
-            signature: Signature {span: signature.span(),
-                params,
-                ret: ret.to_string(),
+            name_spec: NameSpec::Generated {
+                package: package.map(|p| p.value()),
+                class: class.value(),
+                method: method.value(),
             },
+            signature: parse_signature(&sig),
+            exception: exception.map(|e| e.value()),
         })
     }
 }
 
-lazy_static! {
-    static ref TYPE_TO_DESCRIPTOR: HashMap<&'static str, Regex> = [
-        ("()", Regex::new(r"V").unwrap()),
-        ("jint", Regex::new(r"I").unwrap()),
-        ("jlong", Regex::new(r"J").unwrap()),
-        ("jbyte", Regex::new(r"B").unwrap()),
-        ("jboolean", Regex::new(r"Z").unwrap()),
-        ("jchar", Regex::new(r"C").unwrap()),
-        ("jshort", Regex::new(r"S").unwrap()),
-        ("jfloat", Regex::new(r"F").unwrap()),
-        ("jdouble", Regex::new(r"D").unwrap()),
-        ("jobject", Regex::new(r"L.+;").unwrap()),
-        ("jclass", Regex::new(r"Ljava.lang.Class;").unwrap()),
-        ("jthrowable", Regex::new(r"Ljava.lang.Throwable;").unwrap()),
-        ("jstring", Regex::new(r"Ljava.lang.String;").unwrap()),
-        ("jarray", Regex::new(r"\[.+").unwrap()),
-        ("jbooleanArray", Regex::new(r"\[Z").unwrap()),
-        ("jbyteArray", Regex::new(r"\[B").unwrap()),
-        ("jcharArray", Regex::new(r"\[C").unwrap()),
-        ("jshortArray", Regex::new(r"\[S").unwrap()),
-        ("jintArray", Regex::new(r"\[I").unwrap()),
-        ("jlongArray", Regex::new(r"\[J").unwrap()),
-        ("jfloatArray", Regex::new(r"\[F").unwrap()),
-        ("jdoubleArray", Regex::new(r"\[D").unwrap()),
-        ("jobjectArray", Regex::new(r"\[L.+;").unwrap()),
-        ("JByteBuffer", Regex::new(r"java.nio.ByteBuffer").unwrap()),
-        ("JClass", Regex::new(r"java.lang.Class").unwrap()),
-        ("JList", Regex::new(r"java.lang.List").unwrap()),
-        ("JMap", Regex::new(r"java.util.Map").unwrap()),
-        ("JObject", Regex::new(r"L.+;").unwrap()),
-        ("JObjectArray", Regex::new(r"\[java.lang.Object").unwrap()),
-        ("JPrimitiveArray", Regex::new(r"\[[IJBZCSFDV]").unwrap()),
-        ("JString", Regex::new(r"java.lang.String").unwrap()),
-        ("JThrowable", Regex::new(r"java.lang.Throwable").unwrap()),
-    ]
-    .into();
+fn primitive_char(primitive: Primitive) -> char {
+    match primitive {
+        Primitive::Boolean => 'Z',
+        Primitive::Byte => 'B',
+        Primitive::Char => 'C',
+        Primitive::Short => 'S',
+        Primitive::Int => 'I',
+        Primitive::Long => 'J',
+        Primitive::Float => 'F',
+        Primitive::Double => 'D',
+        Primitive::Void => 'V',
+    }
+}
+
+/// Renders a `JavaType` back into a JVM field descriptor, e.g.
+/// `Ljava/lang/String;` or `[I`, for use in overload-disambiguating mangling.
+fn render_field_descriptor(java_type: &JavaType) -> String {
+    match java_type {
+        JavaType::Primitive(primitive) => primitive_char(*primitive).to_string(),
+        JavaType::Object(name) => format!("L{};", name.replace('.', "/")),
+        JavaType::Array(inner) => format!("[{}", render_field_descriptor(inner)),
+        JavaType::Method { .. } => unreachable!("field descriptors don't contain nested methods"),
+    }
+}
+
+/// Mangles a single identifier-like component (a class path or a method
+/// name) per the JNI native method name mangling rules.
+fn mangle_component(component: &str) -> String {
+    let mut mangled = String::new();
+    for c in component.chars() {
+        match c {
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            '/' => mangled.push('_'),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                let mut utf16 = [0u16; 2];
+                for unit in c.encode_utf16(&mut utf16) {
+                    mangled.push_str(&format!("_0{:04x}", unit));
+                }
+            }
+        }
+    }
+    mangled
+}
+
+/// Mangles a fully qualified `package.Class` (or just `Class`) path.
+fn mangle_class_path(package: Option<&str>, class: &str) -> String {
+    let mut path = String::new();
+    if let Some(package) = package {
+        path.push_str(package);
+        path.push('.');
+    }
+    path.push_str(class);
+    mangle_component(&path.replace('.', "/"))
+}
+
+/// Computes the native JNI symbol name for a `package`/`class`/`method`.
+fn jni_symbol(package: Option<&str>, class: &str, method: &str) -> String {
+    format!(
+        "Java_{}_{}",
+        mangle_class_path(package, class),
+        mangle_component(method)
+    )
+}
+
+/// Returns whether `rust_type` is an accepted JNI representation of
+/// `java_type`, or `None` if `rust_type` isn't a type this macro knows how
+/// to convert to/from a descriptor at all.
+fn rust_type_matches_java_type(rust_type: &str, java_type: &JavaType) -> Option<bool> {
+    use JavaType::{Array, Object, Primitive as Prim};
+    use Primitive::*;
+
+    let is_match = match rust_type {
+        "()" => matches!(java_type, Prim(Void)),
+        "jint" => matches!(java_type, Prim(Int)),
+        "jlong" => matches!(java_type, Prim(Long)),
+        "jbyte" => matches!(java_type, Prim(Byte)),
+        "jboolean" => matches!(java_type, Prim(Boolean)),
+        "jchar" => matches!(java_type, Prim(Char)),
+        "jshort" => matches!(java_type, Prim(Short)),
+        "jfloat" => matches!(java_type, Prim(Float)),
+        "jdouble" => matches!(java_type, Prim(Double)),
+        "jobject" | "JObject" => matches!(java_type, Object(_)),
+        "jclass" | "JClass" => matches!(java_type, Object(name) if name == "java.lang.Class"),
+        "jthrowable" | "JThrowable" => {
+            matches!(java_type, Object(name) if name == "java.lang.Throwable")
+        }
+        "jstring" | "JString" => matches!(java_type, Object(name) if name == "java.lang.String"),
+        "jarray" => matches!(java_type, Array(_)),
+        "jbooleanArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Boolean))),
+        "jbyteArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Byte))),
+        "jcharArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Char))),
+        "jshortArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Short))),
+        "jintArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Int))),
+        "jlongArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Long))),
+        "jfloatArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Float))),
+        "jdoubleArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(Double))),
+        "jobjectArray" | "JObjectArray" => matches!(java_type, Array(inner) if matches!(**inner, Object(_))),
+        "JByteBuffer" => matches!(java_type, Object(name) if name == "java.nio.ByteBuffer"),
+        "JList" => matches!(java_type, Object(name) if name == "java.util.List"),
+        "JMap" => matches!(java_type, Object(name) if name == "java.util.Map"),
+        "JPrimitiveArray" => matches!(java_type, Array(inner) if matches!(**inner, Prim(_))),
+        _ => return None,
+    };
+
+    Some(is_match)
+}
+
+/// An idiomatic Rust type accepted via the `jni-verify-types` conversion
+/// layer, as opposed to a raw `jni` type handled directly by
+/// `rust_type_matches_java_type`.
+#[derive(Clone)]
+enum Conversion {
+    String,
+    Uuid,
+    VecOfInt,
+    Vec(Box<Conversion>),
+}
+
+fn fn_arg_type(arg: &FnArg) -> &syn::Type {
+    match arg {
+        FnArg::Typed(pat_type) => &pat_type.ty,
+        FnArg::Receiver(_) => unimplemented!(),
+    }
+}
+
+/// Recognizes `String`, `Vec<T>`, and `Uuid` (ignoring which module they're
+/// imported from), returning `None` for anything else. `Vec<T>` is only
+/// recognized for `i32` (a primitive `int[]`) and for `T` that
+/// `jni-verify-types` actually has an object-array impl for (`String`,
+/// `Uuid`) — `jni-verify-types` has no impl for nested
+/// `Vec<Vec<_>>`, so that's rejected here rather than failing later inside
+/// the generated wrapper.
+fn classify_conversion(ty: &syn::Type) -> Option<Conversion> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+
+    match segment.ident.to_string().as_str() {
+        "String" => Some(Conversion::String),
+        "Uuid" => Some(Conversion::Uuid),
+        "Vec" => {
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            let syn::GenericArgument::Type(inner_ty) = args.args.first()? else {
+                return None;
+            };
+
+            if matches!(inner_ty, syn::Type::Path(p) if p.path.is_ident("i32")) {
+                Some(Conversion::VecOfInt)
+            } else {
+                match classify_conversion(inner_ty)? {
+                    inner @ (Conversion::String | Conversion::Uuid) => {
+                        Some(Conversion::Vec(Box::new(inner)))
+                    }
+                    Conversion::VecOfInt | Conversion::Vec(_) => None,
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Normalizes a descriptor string declared by a `FromJava`/`IntoJava` impl's
+/// `java_type()` (which may be a bare fully qualified class name like
+/// `"java.lang.String"`, or an already-bracketed field descriptor like
+/// `"[Ljava.lang.String;"` or `"[I"`, and may use `/` rather than this
+/// crate's `.`-separated dialect) into a `JavaType`.
+fn parse_trait_java_type(raw: &str) -> JavaType {
+    let normalized = raw.replace('/', ".");
+    let descriptor = if normalized.starts_with(['[', 'L']) {
+        normalized
+    } else {
+        format!("L{normalized};")
+    };
+
+    JavaType::parse_field(&descriptor).unwrap_or_else(|err| {
+        abort!(
+            Span::call_site(),
+            "`jni-verify-types` impl declared an invalid `java_type()` (`{}`) at byte offset {}: {}",
+            raw,
+            err.offset,
+            err.message
+        )
+    })
+}
+
+/// The `JavaType` a given `Conversion` is expected to match in a signature,
+/// read from the corresponding `jni-verify-types` impl's `java_type()` so
+/// this can't silently drift from what the generated wrapper actually
+/// converts with.
+fn conversion_java_type(conversion: &Conversion) -> JavaType {
+    let raw = match conversion {
+        Conversion::String => <String as FromJava<'static>>::java_type(),
+        Conversion::Uuid => <::jni_verify_types::Uuid as FromJava<'static>>::java_type(),
+        Conversion::VecOfInt => <Vec<i32> as FromJava<'static>>::java_type(),
+        Conversion::Vec(inner) => match inner.as_ref() {
+            Conversion::String => <Vec<String> as FromJava<'static>>::java_type(),
+            Conversion::Uuid => <Vec<::jni_verify_types::Uuid> as FromJava<'static>>::java_type(),
+            Conversion::VecOfInt | Conversion::Vec(_) => {
+                unreachable!("classify_conversion only produces Vec<String> or Vec<Uuid>")
+            }
+        },
+    };
+
+    parse_trait_java_type(&raw)
+}
+
+/// The raw `jni` type used at the FFI boundary for a given `Conversion`,
+/// matching the `Raw` associated type of its `jni-verify-types` impl.
+fn conversion_raw_type(conversion: &Conversion, lifetime: &syn::Lifetime) -> syn::Type {
+    match conversion {
+        Conversion::String | Conversion::Uuid => syn::parse_quote!(::jni::objects::JObject<#lifetime>),
+        Conversion::VecOfInt => syn::parse_quote!(::jni::objects::JIntArray<#lifetime>),
+        Conversion::Vec(_) => syn::parse_quote!(::jni::objects::JObjectArray<#lifetime>),
+    }
 }
 
 fn type_path_as_string(path: TypePath) -> String {
@@ -131,24 +580,38 @@ fn function_sig_span(function: &ItemFn) -> Span {
         })
 }
 
-fn ensure_function_name_is_valid_for_jni(function: &ItemFn, name: &str) {
+/// Validates a hand-written `#[verify_signature("method", "(...)...")]`
+/// function name against the real JNI mangling of `method`, accepting
+/// either the short form (`Java_<ClassName>_<mangled method>`) or the
+/// overload-qualified long form (`Java_<ClassName>_<mangled method>__<mangled descriptor>`).
+/// The class/package segment can't be mangled here since it isn't supplied
+/// to this attribute form, so it's matched with a wildcard.
+fn ensure_function_name_is_valid_for_jni(function: &ItemFn, method: &str, params: &[JavaType]) {
     let function_name = function.sig.ident.to_string();
 
-    let name_pattern = Regex::new(&(r"Java_.+".to_string() + "_" + name)).unwrap_or_else(|_|{
-        abort!(
-            function.sig.ident,
-            "Function name {} doesn't match the java method. Expected Java_<ClassName>_{}",
-            function.sig.ident.to_string(),
-            name
-        );
-    });
+    let mangled_method = mangle_component(method);
+    let mangled_params = mangle_component(
+        &params
+            .iter()
+            .map(render_field_descriptor)
+            .collect::<String>(),
+    );
 
-    if !name_pattern.is_match(&function_name) {
+    let pattern = regex::Regex::new(&format!(
+        r"^Java_.+_{}(__{})?$",
+        regex::escape(&mangled_method),
+        regex::escape(&mangled_params)
+    ))
+    .unwrap();
+
+    if !pattern.is_match(&function_name) {
         abort!(
             function.sig.ident,
-            "Function name {} doesn't match the java method. Expected Java_<ClassName>_{}",
-            function.sig.ident.to_string(),
-            name
+            "Function name {} doesn't match the java method. Expected Java_<ClassName>_{} (or Java_<ClassName>_{}__{} for overloads)",
+            function_name,
+            mangled_method,
+            mangled_method,
+            mangled_params
         );
     };
 }
@@ -167,7 +630,10 @@ fn ensure_param_is(function: &ItemFn, param: Option<&FnArg>, param_type: &str) {
     }
 }
 
-fn ensure_parameters_match(arguments: &[&FnArg], signature: &Signature) {
+/// Checks each argument against its signature slot, returning the
+/// idiomatic-type `Conversion` to apply for arguments that aren't a raw
+/// `jni` type (`None` for arguments that are).
+fn ensure_parameters_match(arguments: &[&FnArg], signature: &Signature) -> Vec<Option<Conversion>> {
     if signature.params.len() != arguments.len() {
         abort!(
             signature.span,
@@ -177,97 +643,330 @@ fn ensure_parameters_match(arguments: &[&FnArg], signature: &Signature) {
         );
     }
 
-    if !arguments
+    arguments
         .iter()
-        .map(|arg| {
-            TYPE_TO_DESCRIPTOR
-                .get(fn_arg_as_string(&arg).as_str())
-                .unwrap_or_else(|| {
-                    abort!(
-                        arg,
-                        "Invalid parameter type for JNI method. Can't convert type {} to descriptor",
-                        fn_arg_as_string(&arg).as_str()
-                    )
-                })
-        })
         .zip(&signature.params)
-        .map(|(regex, arg)| { 
-            eprintln!("{}", arg);
-            regex.is_match(&arg)
+        .map(|(arg, java_type)| {
+            let rust_type = fn_arg_as_string(arg);
+
+            match rust_type_matches_java_type(&rust_type, java_type) {
+                Some(true) => None,
+                Some(false) => abort!(
+                    arg,
+                    "Parameter type `{}` doesn't match the corresponding signature type!",
+                    rust_type
+                ),
+                None => {
+                    let conversion = classify_conversion(fn_arg_type(arg)).unwrap_or_else(|| {
+                        abort!(
+                            arg,
+                            "Invalid parameter type for JNI method. Can't convert type {} to descriptor",
+                            rust_type
+                        )
+                    });
+
+                    if conversion_java_type(&conversion) != *java_type {
+                        abort!(
+                            arg,
+                            "Parameter type `{}` doesn't match the corresponding signature type!",
+                            rust_type
+                        );
+                    }
+
+                    Some(conversion)
+                }
+            }
         })
-        .all(|b| b)
-    {
-        abort!(
-            signature.span,
-            "Parameters don't match the rust function!"
-        );
+        .collect()
+}
+
+/// What a JNI function returns: the JNI type directly, an idiomatic type
+/// needing an `IntoJava` conversion, or a `Result<T, E>` (`T` itself being
+/// either a raw JNI type or a converted one) whose `Err` case gets turned
+/// into a thrown Java exception.
+enum ReturnSpec {
+    Direct,
+    Converted(Conversion),
+    Result {
+        ok_type: Box<syn::Type>,
+        ok_conversion: Option<Conversion>,
+    },
+}
+
+/// If `ty` is `Result<T, E>`, returns `T`.
+fn result_ok_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ok_type) => Some(ok_type.clone()),
+        _ => None,
     }
 }
 
-fn ensure_return_types_match(function: &ItemFn, signature: &Signature) {
-    let return_type_string = match function.sig.output.clone() {
-        syn::ReturnType::Default => None,
-        syn::ReturnType::Type(_, ty) => Some(match *ty {
-            syn::Type::Path(path) => type_path_as_string(path),
-            ty => abort!(ty, "Unsupported return type"),
-        }),
+/// Checks a single return-position type (the whole return type, or a
+/// `Result`'s `Ok` type) against `java_type`, returning its `Conversion` if
+/// it isn't a raw `jni` type.
+fn check_return_type(ty: &syn::Type, java_type: &JavaType, error_span: Span) -> Option<Conversion> {
+    let type_string = match ty.clone() {
+        syn::Type::Path(path) => type_path_as_string(path),
+        ty => abort!(ty, "Unsupported return type"),
+    };
+
+    match rust_type_matches_java_type(&type_string, java_type) {
+        Some(true) => None,
+        Some(false) => abort!(
+            error_span,
+            "Return type `{}` doesn't match the signature's return type!",
+            type_string
+        ),
+        None => {
+            let conversion = classify_conversion(ty).unwrap_or_else(|| {
+                abort!(
+                    error_span,
+                    "Invalid return type for JNI method. Can't convert type {} to descriptor",
+                    type_string
+                )
+            });
+
+            if conversion_java_type(&conversion) != *java_type {
+                abort!(
+                    error_span,
+                    "Return type `{}` doesn't match the signature's return type!",
+                    type_string
+                );
+            }
+
+            Some(conversion)
+        }
     }
-    .unwrap_or_else(|| "()".to_string());
-
-    if !TYPE_TO_DESCRIPTOR
-        .get(return_type_string.as_str())
-        .unwrap_or_else(|| {
-            abort!(
-                match &function.sig.output {
-                    ReturnType::Type(_, ty) => ty,
-                    ReturnType::Default => { 
-                        // Default return type is in TYPE_TO_DESCRIPTOR map, so this must be unreachable
-                        unreachable!();
-                    }
-                },
-                "Invalid return type for JNI method. Can't convert type {} to descriptor",
-                return_type_string
-            )
-        })
-        .is_match(&signature.ret)
-    {
-        match &function.sig.output {
-            ReturnType::Type(_, ty) => abort!(
-                ty,
-                "Return type `{}` doesn't match signature `{}`!",
-                return_type_string.as_str(),
-                signature.ret
-            ),
-            
-            ReturnType::Default =>
+}
+
+fn ensure_return_types_match(function: &ItemFn, signature: &Signature) -> ReturnSpec {
+    match &function.sig.output {
+        ReturnType::Default => {
+            if signature.ret != JavaType::Primitive(Primitive::Void) {
                 abort!(
                     function.sig.span(),
-                    "Return type `{}` doesn't match signature `{}`!",
-                    return_type_string.as_str(),
-                    signature.ret
-                )
-                
-        };
+                    "Return type `()` doesn't match the signature's return type!"
+                );
+            }
+            ReturnSpec::Direct
+        }
+        ReturnType::Type(_, ty) => match result_ok_type(ty) {
+            Some(ok_type) => {
+                let ok_conversion = check_return_type(&ok_type, &signature.ret, ok_type.span());
+                ReturnSpec::Result { ok_type: Box::new(ok_type), ok_conversion }
+            }
+            None => match check_return_type(ty, &signature.ret, ty.span()) {
+                None => ReturnSpec::Direct,
+                Some(conversion) => ReturnSpec::Converted(conversion),
+            },
+        },
     }
 }
 
+/// Extracts the plain identifier each parameter is bound to, so it can be
+/// forwarded into a call expression (the declared `FnArg::pat` may carry a
+/// leading `mut`, which isn't valid in an argument position).
+fn fn_arg_idents(function: &ItemFn) -> Vec<Ident> {
+    function
+        .sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                other => abort!(other, "Expected a simple identifier for this JNI parameter"),
+            },
+            FnArg::Receiver(receiver) => abort!(receiver, "`self` isn't a valid JNI parameter"),
+        })
+        .collect()
+}
+
+/// Wraps a function needing idiomatic-type conversions and/or exception
+/// handling into a real `extern "system"` entry point named `target_ident`,
+/// renaming the user's function into a private helper and generating a body
+/// that converts converted parameters with `FromJava`, calls the helper,
+/// then converts/unwraps the result with `IntoJava` and `throw_new`
+/// (via the `JNIEnv` parameter) as needed.
+fn build_wrapper(
+    mut function: ItemFn,
+    target_ident: Ident,
+    param_conversions: Vec<Option<Conversion>>,
+    return_spec: ReturnSpec,
+    exception_class: Option<String>,
+) -> proc_macro2::TokenStream {
+    let call_idents = fn_arg_idents(&function);
+    let env_ident = call_idents[0].clone();
+
+    let lifetime = function
+        .sig
+        .generics
+        .lifetimes()
+        .next()
+        .map(|param| param.lifetime.clone())
+        .unwrap_or_else(|| syn::parse_quote!('local));
+
+    let inner_ident = Ident::new(&format!("__{}_impl", target_ident), function.sig.ident.span());
+
+    let mut inner = function.clone();
+    inner.vis = syn::Visibility::Inherited;
+    inner.sig.ident = inner_ident.clone();
+    inner.sig.abi = None;
+    inner.attrs.retain(|attr| !attr.path().is_ident("no_mangle"));
+
+    let orig_types: Vec<syn::Type> =
+        function.sig.inputs.iter().skip(2).map(|arg| fn_arg_type(arg).clone()).collect();
+
+    let conversion_stmts: Vec<proc_macro2::TokenStream> = call_idents
+        .iter()
+        .skip(2)
+        .zip(&param_conversions)
+        .zip(&orig_types)
+        .filter_map(|((ident, conversion), orig_ty)| {
+            conversion.as_ref().map(|_| {
+                quote!(let #ident = <#orig_ty as ::jni_verify_types::FromJava<#lifetime>>::from_java(&mut #env_ident, #ident);)
+            })
+        })
+        .collect();
+
+    // The inner helper takes `JNIEnv` by value like the original function
+    // did, but `env_ident` is still needed afterwards here (to convert the
+    // return value and/or throw an exception), so hand the inner call an
+    // `unsafe_clone()` rather than moving `env_ident` itself.
+    let call_args: Vec<proc_macro2::TokenStream> = call_idents
+        .iter()
+        .enumerate()
+        .map(|(i, ident)| {
+            if i == 0 {
+                quote!(unsafe { #ident.unsafe_clone() })
+            } else {
+                quote!(#ident)
+            }
+        })
+        .collect();
+
+    for (arg, conversion) in function.sig.inputs.iter_mut().skip(2).zip(&param_conversions) {
+        if let Some(conversion) = conversion {
+            if let FnArg::Typed(pat_type) = arg {
+                *pat_type.ty = conversion_raw_type(conversion, &lifetime);
+            }
+        }
+    }
+
+    let (outer_output, body) = match &return_spec {
+        ReturnSpec::Direct => {
+            let output_ty: syn::Type = match &function.sig.output {
+                ReturnType::Default => syn::parse_quote!(()),
+                ReturnType::Type(_, ty) => (**ty).clone(),
+            };
+            (output_ty, quote!(#inner_ident(#(#call_args),*)))
+        }
+        ReturnSpec::Converted(conversion) => {
+            let raw_ty = conversion_raw_type(conversion, &lifetime);
+            (
+                raw_ty,
+                quote!(::jni_verify_types::IntoJava::into_java(#inner_ident(#(#call_args),*), &mut #env_ident)),
+            )
+        }
+        ReturnSpec::Result { ok_type, ok_conversion } => {
+            let exception_class = exception_class
+                .unwrap_or_else(|| DEFAULT_EXCEPTION_CLASS.to_string())
+                .replace('.', "/");
+            let (raw_ty, ok_arm) = match ok_conversion {
+                Some(conversion) => (
+                    conversion_raw_type(conversion, &lifetime),
+                    quote!(::jni_verify_types::IntoJava::into_java(value, &mut #env_ident)),
+                ),
+                None => (ok_type.as_ref().clone(), quote!(value)),
+            };
+            (
+                raw_ty,
+                quote!(
+                    match #inner_ident(#(#call_args),*) {
+                        Ok(value) => #ok_arm,
+                        Err(err) => {
+                            let _ = #env_ident.throw_new(#exception_class, err.to_string());
+                            Default::default()
+                        }
+                    }
+                ),
+            )
+        }
+    };
+
+    function.sig.ident = target_ident;
+    function.sig.output = syn::parse_quote!(-> #outer_output);
+    function.sig.abi = Some(syn::parse_quote!(extern "system"));
+    if !function.attrs.iter().any(|attr| attr.path().is_ident("no_mangle")) {
+        function.attrs.push(syn::parse_quote!(#[no_mangle]));
+    }
+    function.block = syn::parse_quote!({
+        #(#conversion_stmts)*
+        #body
+    });
+
+    quote!(#inner #function)
+}
+
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn verify_signature(args: TokenStream, input: TokenStream) -> TokenStream {
-    let Args { name, signature } = parse_macro_input!(args as Args);
+    let Args { name_spec, signature, exception } = parse_macro_input!(args as Args);
     let output = input.clone();
-    let function = parse_macro_input!(input as ItemFn);
+    let mut function = parse_macro_input!(input as ItemFn);
 
-    ensure_function_name_is_valid_for_jni(&function, &name.value());
+    if let NameSpec::Explicit(name) = &name_spec {
+        ensure_function_name_is_valid_for_jni(&function, &name.value(), &signature.params);
+    }
 
     let mut arguments = function.sig.inputs.iter().collect::<VecDeque<_>>();
 
     ensure_param_is(&function, arguments.pop_front(), "JNIEnv");
     ensure_param_is(&function, arguments.pop_front(), "JClass");
 
-    ensure_parameters_match(&arguments.make_contiguous(), &signature);
+    let param_conversions = ensure_parameters_match(&arguments.make_contiguous(), &signature);
+
+    let return_spec = ensure_return_types_match(&function, &signature);
+
+    let target_ident = match &name_spec {
+        NameSpec::Explicit(_) => function.sig.ident.clone(),
+        NameSpec::Generated { package, class, method } => {
+            Ident::new(&jni_symbol(package.as_deref(), class, method), function.sig.ident.span())
+        }
+    };
+
+    let needs_wrapper = param_conversions.iter().any(Option::is_some)
+        || matches!(return_spec, ReturnSpec::Converted(_) | ReturnSpec::Result { .. });
 
-    ensure_return_types_match(&function, &signature);
+    if needs_wrapper {
+        return build_wrapper(function, target_ident, param_conversions, return_spec, exception).into();
+    }
+
+    match &name_spec {
+        NameSpec::Explicit(_) => output,
+        NameSpec::Generated { .. } => {
+            let already_no_mangle = function.attrs.iter().any(|attr| attr.path().is_ident("no_mangle"));
+
+            if function.sig.ident == target_ident && already_no_mangle {
+                return output;
+            }
 
-    output
+            function.sig.ident = target_ident;
+            if !already_no_mangle {
+                function.attrs.push(syn::parse_quote!(#[no_mangle]));
+            }
+            function.sig.abi = Some(syn::parse_quote!(extern "system"));
+
+            quote!(#function).into()
+        }
+    }
 }
@@ -8,8 +8,9 @@ use jni::sys::jfloat;
 use jni::sys::jint;
 use jni::sys::jstring;
 use jni::JNIEnv;
+use uuid::Uuid;
 
-#[verify_signature("foo", "(Lsome.package.Foo;asdI)Ljava.lang.Foo;")]
+#[verify_signature("foo", "(Lsome.package.Foo;I)F")]
 #[no_mangle]
 pub extern "system" fn Java_World_foo<'local>(
     mut _env: JNIEnv<'local>,
@@ -42,7 +43,7 @@ pub extern "system" fn Java_Test_foo3<'local>(
 
 #[verify_signature("foo4_123_d____", "(Ljava.lang.String;F)V")]
 #[no_mangle]
-pub extern "system" fn Java_Test_foo4_123_d____<'local>(
+pub extern "system" fn Java_Test_foo4_1123_1d_1_1_1_1<'local>(
     mut _env: JNIEnv<'local>,
     _class: JClass<'local>,
     _input: JString<'local>,
@@ -50,3 +51,61 @@ pub extern "system" fn Java_Test_foo4_123_d____<'local>(
 ) {
     unimplemented!()
 }
+
+#[verify_signature(package = "some.package", class = "Foo", method = "bar", sig = "(I)V")]
+pub fn bar<'local>(mut _env: JNIEnv<'local>, _class: JClass<'local>, _i: jint) {
+    unimplemented!()
+}
+
+// Overloaded methods need the `__<mangled descriptor>` suffix to disambiguate.
+#[verify_signature("foo5", "(I)V")]
+#[no_mangle]
+pub extern "system" fn Java_Test_foo5<'local>(
+    mut _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    _i: jint,
+) {
+    unimplemented!()
+}
+
+#[verify_signature("foo5", "(Ljava.lang.String;)V")]
+#[no_mangle]
+pub extern "system" fn Java_Test_foo5__Ljava_lang_String_2<'local>(
+    mut _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    _s: JString<'local>,
+) {
+    unimplemented!()
+}
+
+#[verify_signature(
+    package = "some.package",
+    class = "Foo",
+    method = "checked",
+    sig = "(I)I",
+    exception = "java.lang.IllegalArgumentException"
+)]
+pub fn checked<'local>(
+    mut _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    _i: jint,
+) -> Result<jint, String> {
+    unimplemented!()
+}
+
+// `String`, `Vec<T>` and `Uuid` parameters/returns are converted via
+// `jni-verify-types`'s `FromJava`/`IntoJava` rather than matched directly.
+#[verify_signature(
+    package = "some.package",
+    class = "Foo",
+    method = "greet",
+    sig = "(Ljava.lang.String;[I)Ljava.util.UUID;"
+)]
+pub fn greet<'local>(
+    mut _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    _name: String,
+    _scores: Vec<i32>,
+) -> Uuid {
+    unimplemented!()
+}